@@ -2,24 +2,104 @@ extern crate piston_window;
 extern crate rand;
 
 mod side;
+mod theme;
 mod view;
 
+use std::fs;
+
 use side::Side;
+use theme::THEMES;
 use view::View;
 use piston_window::keyboard::Key;
 use rand::Rng;
 
+const BEST_TIME_FILE: &str = "bestscore.txt";
+
+fn load_best_time() -> Option<f64> {
+    fs::read_to_string(BEST_TIME_FILE)
+        .ok()
+        .and_then(|s| s.trim().parse().ok())
+}
+
+fn save_best_time(time: f64) {
+    let _ = fs::write(BEST_TIME_FILE, format!("{}", time));
+}
+
 enum GameState {
     Init,
     Preparing { time_to_start: f64 },
-    Running { elapsed_time: f64, side: Side },
+    Running { elapsed_time: f64, side: Side, ai_delay: f64 },
     Result { elapsed_time: f64, is_correct: bool },
     FalseStart,
+    MatchOver { won: bool },
+}
+
+// How long the AI waits while no round is preparing or running (Init, a
+// result, a false start or a finished match) before auto-pressing Space.
+const AI_IDLE_DELAY: f64 = 0.6;
+
+struct Match {
+    target: u32,
+    wins: u32,
+    losses: u32,
+    rounds_played: u32,
+    total_time: f64,
+}
+
+impl Match {
+    fn new(target: u32) -> Self {
+        Match {
+            target: target,
+            wins: 0,
+            losses: 0,
+            rounds_played: 0,
+            total_time: 0.0,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.wins = 0;
+        self.losses = 0;
+        self.rounds_played = 0;
+        self.total_time = 0.0;
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    fn prep_range(&self) -> (f64, f64) {
+        match *self {
+            Difficulty::Easy => (1.5, 3.0),
+            Difficulty::Normal => (1.0, 2.5),
+            Difficulty::Hard => (0.5, 2.0),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match *self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
 }
 
 pub struct App {
     game_state: GameState,
     rng: Box<Rng>,
+    theme_idx: usize,
+    history: Vec<f64>,
+    best_time: Option<f64>,
+    match_: Match,
+    difficulty: Difficulty,
+    ai_mode: bool,
+    ai_idle_timer: f64,
 }
 
 impl App {
@@ -27,9 +107,39 @@ impl App {
         App {
             game_state: GameState::Init,
             rng: Box::new(rand::thread_rng()),
+            theme_idx: 0,
+            history: Vec::new(),
+            best_time: load_best_time(),
+            match_: Match::new(3),
+            difficulty: Difficulty::Normal,
+            ai_mode: false,
+            ai_idle_timer: 0.0,
+        }
+    }
+
+    fn start_preparing(&mut self) {
+        let (min, max) = self.difficulty.prep_range();
+        let time_to_start = self.rng.gen_range(min, max);
+        self.game_state = GameState::Preparing {
+            time_to_start: time_to_start,
         }
     }
 
+    fn round_count(&self) -> usize {
+        self.history.len()
+    }
+
+    fn mean_time(&self) -> f64 {
+        self.history.iter().sum::<f64>() / self.history.len() as f64
+    }
+
+    fn stddev_time(&self) -> f64 {
+        let mean = self.mean_time();
+        let variance = self.history.iter().map(|t| (t - mean).powi(2)).sum::<f64>()
+            / self.history.len() as f64;
+        variance.sqrt()
+    }
+
     pub fn update(&mut self, dt: f64) {
         match self.game_state {
             GameState::Preparing { time_to_start } => {
@@ -43,6 +153,7 @@ impl App {
                         } else {
                             Side::Right
                         },
+                        ai_delay: self.rng.gen_range(0.18, 0.30),
                     }
                 } else {
                     self.game_state = GameState::Preparing {
@@ -50,67 +161,203 @@ impl App {
                     }
                 }
             }
-            GameState::Running { elapsed_time, side } => {
-                self.game_state = GameState::Running {
-                    elapsed_time: elapsed_time + dt,
-                    side: side,
+            GameState::Running {
+                elapsed_time,
+                side,
+                ai_delay,
+            } => {
+                let elapsed_time = elapsed_time + dt;
+                if self.ai_mode && elapsed_time >= ai_delay {
+                    self.guess(side);
+                } else {
+                    self.game_state = GameState::Running {
+                        elapsed_time: elapsed_time,
+                        side: side,
+                        ai_delay: ai_delay,
+                    }
                 }
             }
             _ => (),
         }
+
+        if self.ai_mode {
+            match self.game_state {
+                GameState::Preparing { .. } | GameState::Running { .. } => {
+                    self.ai_idle_timer = 0.0;
+                }
+                _ => {
+                    self.ai_idle_timer += dt;
+                    if self.ai_idle_timer >= AI_IDLE_DELAY {
+                        self.ai_idle_timer = 0.0;
+                        self.key(Key::Space);
+                    }
+                }
+            }
+        }
     }
 
     pub fn key(&mut self, key: Key) {
         match (&self.game_state, key) {
+            (_, Key::T) => self.theme_idx = (self.theme_idx + 1) % THEMES.len(),
+            (_, Key::A) => self.ai_mode = !self.ai_mode,
+            (&GameState::Init { .. }, Key::D1) |
+            (&GameState::MatchOver { .. }, Key::D1) => self.match_.target = 1,
+            (&GameState::Init { .. }, Key::D3) |
+            (&GameState::MatchOver { .. }, Key::D3) => self.match_.target = 3,
+            (&GameState::Init { .. }, Key::D5) |
+            (&GameState::MatchOver { .. }, Key::D5) => self.match_.target = 5,
+            (&GameState::Init { .. }, Key::E) |
+            (&GameState::MatchOver { .. }, Key::E) => self.difficulty = Difficulty::Easy,
+            (&GameState::Init { .. }, Key::N) |
+            (&GameState::MatchOver { .. }, Key::N) => self.difficulty = Difficulty::Normal,
+            (&GameState::Init { .. }, Key::H) |
+            (&GameState::MatchOver { .. }, Key::H) => self.difficulty = Difficulty::Hard,
             (&GameState::Preparing { .. }, _) => self.game_state = GameState::FalseStart,
-            (&GameState::Running { elapsed_time, side }, Key::Left) |
-            (&GameState::Running { elapsed_time, side }, Key::Right) => {
+            (&GameState::Running { .. }, Key::Left) => self.guess(Side::Left),
+            (&GameState::Running { .. }, Key::Right) => self.guess(Side::Right),
+            (&GameState::Init { .. }, Key::Space) |
+            (&GameState::MatchOver { .. }, Key::Space) => {
+                self.match_.reset();
+                self.start_preparing()
+            }
+            (&GameState::Result { .. }, Key::Space) |
+            (&GameState::FalseStart, Key::Space) => self.start_preparing(),
+            _ => (),
+        }
+    }
+
+    // treats a click/tap at (x, y) in a window of width view_w as a Left
+    // guess in the left half and a Right guess in the right half, reusing
+    // the same state transitions as key
+    pub fn press_at(&mut self, x: f64, y: f64, view_w: f64) {
+        let _ = y;
+        let guessed_side = if x < view_w / 2.0 {
+            Side::Left
+        } else {
+            Side::Right
+        };
+
+        match self.game_state {
+            GameState::Preparing { .. } => self.game_state = GameState::FalseStart,
+            GameState::Running { .. } => self.guess(guessed_side),
+            _ => (),
+        }
+    }
+
+    fn guess(&mut self, guessed_side: Side) {
+        if let GameState::Running { elapsed_time, side, .. } = self.game_state {
+            let is_correct = guessed_side == side;
+
+            // The AI/demo mode drives this same path with a synthetic
+            // reaction time; keep it from polluting the real player's
+            // session stats, best time and match tally.
+            if self.ai_mode {
                 self.game_state = GameState::Result {
                     elapsed_time: elapsed_time,
-                    is_correct: match (key, side) {
-                        (Key::Left, Side::Left) | (Key::Right, Side::Right) => true,
-                        _ => false,
-                    },
+                    is_correct: is_correct,
+                };
+                return;
+            }
+
+            if is_correct {
+                self.history.push(elapsed_time);
+                if self.best_time.map_or(true, |best| elapsed_time < best) {
+                    self.best_time = Some(elapsed_time);
+                    save_best_time(elapsed_time);
                 }
             }
-            (&GameState::Init { .. }, Key::Space) |
-            (&GameState::Result { .. }, Key::Space) |
-            (&GameState::FalseStart, Key::Space) => {
-                self.game_state = GameState::Preparing { time_to_start: 1.0 }
+
+            self.match_.rounds_played += 1;
+            self.match_.total_time += elapsed_time;
+            if is_correct {
+                self.match_.wins += 1;
+            } else {
+                self.match_.losses += 1;
+            }
+
+            self.game_state = if self.match_.rounds_played >= self.match_.target {
+                GameState::MatchOver {
+                    won: self.match_.wins > self.match_.losses,
+                }
+            } else {
+                GameState::Result {
+                    elapsed_time: elapsed_time,
+                    is_correct: is_correct,
+                }
             }
-            _ => (),
         }
     }
 
     pub fn view(&self) -> View {
+        let theme = THEMES[self.theme_idx];
         match self.game_state {
             GameState::Init => View {
-                text: String::from("Press <Space> to start"),
+                text: format!(
+                    "Press <Space> to start \u{b7} best of {} (<1>/<3>/<5>) \u{b7} {} (<E>/<N>/<H>)",
+                    self.match_.target,
+                    self.difficulty.name()
+                ),
                 side: None,
+                theme: theme,
             },
             GameState::Preparing { time_to_start } => View {
                 text: format!("time to start: {:.*}", 2, time_to_start),
                 side: None,
+                theme: theme,
             },
-            GameState::Running { elapsed_time, side } => View {
+            GameState::Running { elapsed_time, side, .. } => View {
                 text: format!("elapsed time: {:.*}", 2, elapsed_time),
                 side: Some(side),
+                theme: theme,
             },
             GameState::Result {
                 elapsed_time,
                 is_correct,
-            } => View {
+            } => {
+                let stats = if self.history.is_empty() {
+                    String::new()
+                } else {
+                    format!(
+                        " \u{2014} best {:.*}s \u{b7} avg {:.*}s \u{b7} \u{3c3} {:.*}s \u{b7} {} rounds",
+                        2,
+                        self.best_time.unwrap_or(elapsed_time),
+                        2,
+                        self.mean_time(),
+                        2,
+                        self.stddev_time(),
+                        self.round_count()
+                    )
+                };
+                View {
+                    text: format!(
+                        "You {}! Elapsed time: {:.*}{}",
+                        if is_correct { "win" } else { "lose" },
+                        2,
+                        elapsed_time,
+                        stats
+                    ),
+                    side: None,
+                    theme: theme,
+                }
+            }
+            GameState::FalseStart => View {
+                text: String::from("False start!"),
+                side: None,
+                theme: theme,
+            },
+            GameState::MatchOver { won } => View {
                 text: format!(
-                    "You {}! Elapsed time: {:.*}",
-                    if is_correct { "win" } else { "lose" },
+                    "Match {}! {} - {} \u{b7} total time {:.*}s \u{b7} <Space> to replay, best of {} (<1>/<3>/<5>), {} (<E>/<N>/<H>)",
+                    if won { "won" } else { "lost" },
+                    self.match_.wins,
+                    self.match_.losses,
                     2,
-                    elapsed_time
+                    self.match_.total_time,
+                    self.match_.target,
+                    self.difficulty.name()
                 ),
                 side: None,
-            },
-            GameState::FalseStart => View {
-                text: String::from("False start!"),
-                side: None,
+                theme: theme,
             },
         }
     }
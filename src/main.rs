@@ -20,11 +20,15 @@ fn main() {
     let mut glyphs = Glyphs::new(font, factory, texture_settings).unwrap();
 
     let mut app = App::new();
+    let mut cursor = [0.0, 0.0];
 
     while let Some(e) = window.next() {
         e.update(|args| app.update(args.dt));
-        e.press(|button| if let Button::Keyboard(key) = button {
-            app.key(key)
+        e.mouse_cursor(|pos| cursor = pos);
+        e.press(|button| match button {
+            Button::Keyboard(key) => app.key(key),
+            Button::Mouse(_) => app.press_at(cursor[0], cursor[1], window.size().width as f64),
+            _ => (),
         });
         window.draw_2d(&e, |c, g| app.view().render(c, g, &mut glyphs));
     }
@@ -0,0 +1,28 @@
+#[derive(Clone, Copy)]
+pub struct Theme {
+    pub bg_color: [f32; 4],
+    pub text_color: [f32; 4],
+    pub active_side_color: [f32; 4],
+    pub idle_side_color: [f32; 4],
+}
+
+pub static THEMES: [Theme; 3] = [
+    Theme {
+        bg_color: [0.9, 0.9, 0.9, 1.0],
+        text_color: [0.1, 0.1, 0.1, 1.0],
+        active_side_color: [0.8, 0.1, 0.1, 1.0],
+        idle_side_color: [0.6, 0.6, 0.6, 1.0],
+    },
+    Theme {
+        bg_color: [0.1, 0.1, 0.1, 1.0],
+        text_color: [0.9, 0.9, 0.9, 1.0],
+        active_side_color: [0.8, 0.2, 0.2, 1.0],
+        idle_side_color: [0.3, 0.3, 0.3, 1.0],
+    },
+    Theme {
+        bg_color: [0.0, 0.0, 0.0, 1.0],
+        text_color: [1.0, 1.0, 0.0, 1.0],
+        active_side_color: [0.0, 0.8, 0.8, 1.0],
+        idle_side_color: [0.4, 0.4, 0.4, 1.0],
+    },
+];
@@ -1,9 +1,11 @@
 use piston_window::*;
 use side::Side;
+use theme::Theme;
 
 pub struct View {
     pub text: String,
     pub side: Option<Side>,
+    pub theme: Theme,
 }
 
 impl View {
@@ -23,20 +25,23 @@ impl View {
         let side_height = (h as f64) - side_top_padding - padding;
         let side_width = (w as f64) * 0.5 - padding * 1.5;
 
-        // which rectangle will be brighter
-        let left_color_difference = match self.side {
-            None => 0.0,
-            Some(Side::Left) => 0.125,
-            Some(Side::Right) => -0.125,
+        // which rectangle is the active one (the side to press)
+        let left_color = match self.side {
+            Some(Side::Left) => self.theme.active_side_color,
+            _ => self.theme.idle_side_color,
+        };
+        let right_color = match self.side {
+            Some(Side::Right) => self.theme.active_side_color,
+            _ => self.theme.idle_side_color,
         };
 
         // drawing part
 
         // clear the screen
-        clear([0.5, 0.5, 0.5, 1.0], g);
+        clear(self.theme.bg_color, g);
 
         // draw text
-        text::Text::new(font_size).draw(
+        text::Text::new_color(self.theme.text_color, font_size).draw(
             &self.text,
             glyphs,
             &c.draw_state,
@@ -46,7 +51,7 @@ impl View {
 
         // draw left rectangle
         rectangle(
-            [0.5 + left_color_difference, 0.0, 0.0, 1.0],
+            left_color,
             [padding, side_top_padding, side_width, side_height],
             c.transform,
             g,
@@ -54,7 +59,7 @@ impl View {
 
         // draw right rectangle
         rectangle(
-            [0.5 - left_color_difference, 0.0, 0.0, 1.0],
+            right_color,
             [
                 side_width + padding * 2.0,
                 side_top_padding,